@@ -17,6 +17,7 @@ use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::env;
 use std::num::NonZeroU32;
 use std::path::Path;
@@ -26,11 +27,14 @@ use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 use axum::{
-    routing::get,
+    routing::{get, post},
     Router,
     Json,
     http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
 };
+use futures_util::StreamExt;
+use tokio_stream::wrappers::BroadcastStream;
 use serde_json::json;
 
 /// The Odds API event structure
@@ -71,8 +75,19 @@ pub struct Outcome {
     pub point: Option<f64>,
 }
 
+/// The Odds API historical endpoint wrapper: a timestamped envelope around the
+/// same event list returned by the live endpoint.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct HistoricalSnapshot {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub previous_timestamp: Option<DateTime<Utc>>,
+    pub next_timestamp: Option<DateTime<Utc>>,
+    pub data: Vec<OddsApiEvent>,
+}
+
 /// Normalized odds snapshot for storage
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OddsSnapshot {
     pub time: DateTime<Utc>,
     pub game_id: Uuid,
@@ -87,6 +102,168 @@ pub struct OddsSnapshot {
     pub away_price: Option<i32>,
     pub over_price: Option<i32>,
     pub under_price: Option<i32>,
+    /// No-vig fair probabilities, populated when both sides of the market are priced.
+    /// Left `None` for one-sided markets (never assumed 50/50).
+    #[serde(default)]
+    pub home_fair_prob: Option<f64>,
+    #[serde(default)]
+    pub away_fair_prob: Option<f64>,
+    #[serde(default)]
+    pub over_fair_prob: Option<f64>,
+    #[serde(default)]
+    pub under_fair_prob: Option<f64>,
+    /// Sum of the two raw implied probabilities (the overround); `hold = overround - 1`.
+    #[serde(default)]
+    pub overround: Option<f64>,
+}
+
+/// OHLC candle aggregating how a single line moved over a fixed time window.
+///
+/// Buckets `OddsSnapshot` rows by `(game_id, bookmaker, market_type, period)` and
+/// records open/high/low/close of the numeric line (`home_line` for spreads,
+/// `total_line` for totals) plus the open/close of the associated prices. A window
+/// with a single snapshot yields `open == high == low == close`; windows with no
+/// snapshots are gap-filled by carrying the previous close so charts stay continuous.
+#[derive(Debug, Serialize, Clone)]
+pub struct OddsCandle {
+    pub bucket: DateTime<Utc>,
+    pub game_id: Uuid,
+    pub bookmaker: String,
+    pub market_type: String,
+    pub period: String,
+    pub open_line: Option<f64>,
+    pub high_line: Option<f64>,
+    pub low_line: Option<f64>,
+    pub close_line: Option<f64>,
+    pub open_price: Option<i32>,
+    pub close_price: Option<i32>,
+    /// True when the bucket had no snapshots and was filled from the prior close.
+    pub gap_filled: bool,
+}
+
+/// Cross-book consensus for a single `(market_type, period)` within an event.
+///
+/// Built from the per-book [`OddsSnapshot`]s after removing each book's vig: the
+/// fair probabilities and line are the (sharp-weighted) median across all books
+/// quoting the market. For `totals` the `home_*` fields carry the Over side and the
+/// `away_*` fields the Under side.
+#[derive(Debug, Serialize, Clone)]
+pub struct ConsensusSnapshot {
+    pub time: DateTime<Utc>,
+    pub game_id: Uuid,
+    pub external_id: String,
+    pub market_type: String,
+    pub period: String,
+    pub consensus_line: Option<f64>,
+    pub home_fair_prob: Option<f64>,
+    pub away_fair_prob: Option<f64>,
+    pub home_fair_price: Option<i32>,
+    pub away_fair_price: Option<i32>,
+    pub book_count: usize,
+}
+
+/// Outcome of extracting a batch of API events into snapshots.
+///
+/// `all` is every market extracted this cycle; `fresh` is the subset that cleared the
+/// staleness/out-of-order dedup gate. Consensus and steam detection read `all` so the
+/// cross-book median can't be skewed by whichever 1–2 books happened to re-quote this poll,
+/// while only `fresh` is stored and published to `odds.live`.
+pub struct ProcessedSnapshots {
+    pub all: Vec<OddsSnapshot>,
+    pub fresh: Vec<OddsSnapshot>,
+}
+
+/// One down-sampled point in a single book's line-movement history.
+///
+/// For `totals` the `line`/`price` carry the Over side; otherwise the home side, matching
+/// the projection used by the candle aggregation.
+#[derive(Debug, Serialize, Clone)]
+pub struct LineHistoryPoint {
+    pub time: DateTime<Utc>,
+    pub line: Option<f64>,
+    pub price: Option<i32>,
+}
+
+/// A game's line-movement history for one `(bookmaker, market_type, period)`.
+///
+/// Carries the time-ordered [`LineHistoryPoint`]s plus the derived open line, current line,
+/// and net movement (current minus open) so a caller can chart the market's evolution.
+#[derive(Debug, Serialize, Clone)]
+pub struct LineHistorySeries {
+    pub bookmaker: String,
+    pub market_type: String,
+    pub period: String,
+    pub open_line: Option<f64>,
+    pub current_line: Option<f64>,
+    pub net_movement: Option<f64>,
+    pub points: Vec<LineHistoryPoint>,
+}
+
+/// Convert an American price to its implied probability.
+/// `price > 0 -> 100/(price+100)`; `price < 0 -> -price/(-price+100)`.
+fn american_to_implied_prob(price: i32) -> Option<f64> {
+    match price {
+        0 => None,
+        p if p > 0 => Some(100.0 / (p as f64 + 100.0)),
+        p => Some((-p as f64) / ((-p as f64) + 100.0)),
+    }
+}
+
+/// Convert a probability back to a (rounded) American price.
+fn implied_prob_to_american(prob: f64) -> Option<i32> {
+    if !(0.0..=1.0).contains(&prob) || prob == 0.0 {
+        return None;
+    }
+    if prob >= 0.5 {
+        Some(-((prob / (1.0 - prob)) * 100.0).round() as i32)
+    } else {
+        Some((((1.0 - prob) / prob) * 100.0).round() as i32)
+    }
+}
+
+/// Remove the bookmaker margin from a two-way market.
+///
+/// Returns `(fair_prob_a, fair_prob_b, overround)` once both sides are priced;
+/// one-sided markets return `None` (they can't be de-vigged, and must not be
+/// assumed 50/50).
+fn devig_two_way(price_a: Option<i32>, price_b: Option<i32>) -> Option<(f64, f64, f64)> {
+    let pa = american_to_implied_prob(price_a?)?;
+    let pb = american_to_implied_prob(price_b?)?;
+    let overround = pa + pb;
+    if overround <= 0.0 {
+        return None;
+    }
+    Some((pa / overround, pb / overround, overround))
+}
+
+/// Per-book row feeding a consensus reduction (internal to `compute_consensus`).
+struct ConsensusRow {
+    weight: u32,
+    fair_a: f64,
+    fair_b: f64,
+    line: Option<f64>,
+}
+
+/// Weighted median of `(value, weight)` pairs. Each value is repeated by its
+/// integer weight so sharper books pull the median toward their quote. Returns
+/// `None` when there are no values.
+fn weighted_median<I: IntoIterator<Item = (f64, u32)>>(items: I) -> Option<f64> {
+    let mut expanded: Vec<f64> = Vec::new();
+    for (value, weight) in items {
+        for _ in 0..weight.max(1) {
+            expanded.push(value);
+        }
+    }
+    if expanded.is_empty() {
+        return None;
+    }
+    expanded.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = expanded.len() / 2;
+    if expanded.len() % 2 == 1 {
+        Some(expanded[mid])
+    } else {
+        Some((expanded[mid - 1] + expanded[mid]) / 2.0)
+    }
 }
 
 /// Configuration
@@ -100,6 +277,34 @@ pub struct Config {
     pub health_port: u16,
     /// If true, run once and exit (no polling loop)
     pub run_once: bool,
+    /// Candle bucket width in seconds for the odds-candle aggregation task (e.g. 60/300/900).
+    pub candle_bucket_seconds: u64,
+    /// Minimum trigram similarity (0..1) to accept a fuzzy team match (default 0.45).
+    pub team_match_threshold: f64,
+    /// Dedup line granularity (points): lines are rounded to multiples of this before
+    /// comparison, so a move of at least this much always counts as a change and smaller
+    /// moves count only when they cross a rounding boundary. `0.0` means any difference counts.
+    pub dedup_line_threshold: f64,
+    /// Max staleness (seconds) before an unchanged market is force-flushed anyway, so a
+    /// frozen line still produces an occasional heartbeat row.
+    pub forced_flush_seconds: i64,
+    /// Sliding window (minutes) over which a steam move is measured.
+    pub steam_window_minutes: i64,
+    /// Minimum line move (points, either direction) within the window to fire a spread/total steam alert.
+    pub steam_threshold: f64,
+    /// Minimum fair-probability jump within the window to fire an h2h steam alert.
+    pub steam_prob_threshold: f64,
+    /// Minimum number of books quoting before a move is trusted as steam.
+    pub steam_min_books: usize,
+    /// Debounce window (seconds): suppress repeat alerts for the same market key.
+    pub steam_debounce_seconds: i64,
+    /// Backfill window start (`BACKFILL_FROM`). When set, the service drives the historical
+    /// endpoint from here toward `backfill_to` and exits instead of polling live.
+    pub backfill_from: Option<DateTime<Utc>>,
+    /// Backfill window end (`BACKFILL_TO`).
+    pub backfill_to: Option<DateTime<Utc>>,
+    /// Step between historical snapshots in seconds (`BACKFILL_STEP_SECONDS`, default 300).
+    pub backfill_step_seconds: u64,
 }
 
 impl Config {
@@ -168,8 +373,71 @@ impl Config {
             run_once: env::var("RUN_ONCE")
                 .unwrap_or_else(|_| "false".to_string())
                 .to_lowercase() == "true",
+            candle_bucket_seconds: env::var("CANDLE_BUCKET_SECONDS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .unwrap_or(300)
+                // A zero-width bucket would wedge the candle gap-fill loop; keep it >= 1s.
+                .max(1),
+            team_match_threshold: env::var("TEAM_MATCH_THRESHOLD")
+                .unwrap_or_else(|_| "0.45".to_string())
+                .parse()
+                .unwrap_or(0.45),
+            dedup_line_threshold: env::var("DEDUP_LINE_THRESHOLD")
+                .unwrap_or_else(|_| "0.0".to_string())
+                .parse()
+                .unwrap_or(0.0),
+            forced_flush_seconds: env::var("FORCED_FLUSH_SECONDS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .unwrap_or(600),
+            steam_window_minutes: env::var("STEAM_WINDOW_MINUTES")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            steam_threshold: env::var("STEAM_THRESHOLD")
+                .unwrap_or_else(|_| "1.0".to_string())
+                .parse()
+                .unwrap_or(1.0),
+            steam_prob_threshold: env::var("STEAM_PROB_THRESHOLD")
+                .unwrap_or_else(|_| "0.03".to_string())
+                .parse()
+                .unwrap_or(0.03),
+            steam_min_books: env::var("STEAM_MIN_BOOKS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .unwrap_or(3),
+            steam_debounce_seconds: env::var("STEAM_DEBOUNCE_SECONDS")
+                .unwrap_or_else(|_| "120".to_string())
+                .parse()
+                .unwrap_or(120),
+            backfill_from: parse_optional_timestamp("BACKFILL_FROM")?,
+            backfill_to: parse_optional_timestamp("BACKFILL_TO")?,
+            backfill_step_seconds: env::var("BACKFILL_STEP_SECONDS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .unwrap_or(300),
         })
     }
+
+    /// True when a backfill window is configured (`BACKFILL_FROM` set). Backfill runs
+    /// the historical loop to completion and exits, like `run_once`.
+    pub fn is_backfill(&self) -> bool {
+        self.backfill_from.is_some()
+    }
+}
+
+/// Parse an optional RFC3339 timestamp env var, erroring only when it's set but unparseable.
+fn parse_optional_timestamp(var: &str) -> Result<Option<DateTime<Utc>>> {
+    match env::var(var) {
+        Ok(v) if !v.trim().is_empty() => {
+            let ts = DateTime::parse_from_rfc3339(v.trim())
+                .with_context(|| format!("{} must be an RFC3339 timestamp, got '{}'", var, v))?
+                .with_timezone(&Utc);
+            Ok(Some(ts))
+        }
+        _ => Ok(None),
+    }
 }
 
 /// Read a secret from Docker secret file - REQUIRED, NO fallbacks
@@ -251,6 +519,558 @@ impl GameCache {
     }
 }
 
+/// Per-key record of the last snapshot we emitted for a market.
+#[derive(Clone, Copy)]
+struct LastWrite {
+    last_update: Option<DateTime<Utc>>,
+    content_hash: u64,
+    /// Wall-clock time the last snapshot for this key was emitted (for forced flush).
+    last_emitted: DateTime<Utc>,
+}
+
+/// Thread-safe last-write cache used to suppress unchanged/out-of-order snapshots.
+///
+/// Mirrors [`GameCache`]'s double-checked-locking `RwLock<HashMap>` pattern, keyed by
+/// `(game_id, bookmaker, market_type, period)`. Stores the last seen `Market.last_update`
+/// and a content hash of the line/prices so the poller only re-emits a market when it
+/// actually moved.
+#[derive(Clone)]
+pub struct SnapshotCache {
+    inner: Arc<RwLock<HashMap<String, LastWrite>>>,
+}
+
+impl SnapshotCache {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    fn key(game_id: Uuid, bookmaker: &str, market_type: &str, period: &str) -> String {
+        format!("{}|{}|{}|{}", game_id, bookmaker, market_type, period)
+    }
+
+    /// Decide whether a freshly extracted snapshot should be emitted, updating the cache
+    /// when it should. Returns `false` (and leaves the cache untouched) when the incoming
+    /// `last_update` is not newer than the cached one, when it is newer but the numeric
+    /// content is identical, or when it is strictly older (an out-of-order arrival).
+    ///
+    /// An unchanged market is still emitted ("force-flushed") once more than
+    /// `flush_interval` has elapsed since its last emission, so a frozen line keeps a
+    /// heartbeat in the hypertable.
+    pub async fn observe(
+        &self,
+        game_id: Uuid,
+        bookmaker: &str,
+        market_type: &str,
+        period: &str,
+        last_update: Option<DateTime<Utc>>,
+        content_hash: u64,
+        now: DateTime<Utc>,
+        flush_interval: chrono::Duration,
+    ) -> bool {
+        let key = Self::key(game_id, bookmaker, market_type, period);
+
+        // Fast path: a read lock is enough to reject a clearly stale/unchanged arrival.
+        {
+            let cache = self.inner.read().await;
+            if let Some(prev) = cache.get(&key) {
+                if !Self::should_emit(prev, last_update, content_hash, now, flush_interval) {
+                    return false;
+                }
+            }
+        }
+
+        // Write path: re-check under the write lock in case another task advanced the key.
+        let mut cache = self.inner.write().await;
+        if let Some(prev) = cache.get(&key) {
+            if !Self::should_emit(prev, last_update, content_hash, now, flush_interval) {
+                return false;
+            }
+        }
+        cache.insert(key, LastWrite { last_update, content_hash, last_emitted: now });
+        true
+    }
+
+    /// Whether the incoming snapshot is a genuine move (or a forced flush) relative to `prev`,
+    /// and not an out-of-order arrival.
+    fn should_emit(
+        prev: &LastWrite,
+        last_update: Option<DateTime<Utc>>,
+        content_hash: u64,
+        now: DateTime<Utc>,
+        flush_interval: chrono::Duration,
+    ) -> bool {
+        // Out-of-order arrivals are always dropped so we never overwrite fresher data.
+        if let (Some(old), Some(new)) = (prev.last_update, last_update) {
+            if new < old {
+                return false;
+            }
+        }
+
+        let changed = match (prev.last_update, last_update) {
+            (Some(old), Some(new)) => new > old && content_hash != prev.content_hash,
+            _ => content_hash != prev.content_hash,
+        };
+        let forced = now - prev.last_emitted >= flush_interval;
+        changed || forced
+    }
+
+    /// Clear old entries to prevent unbounded memory growth (see [`GameCache::cleanup`]).
+    pub async fn cleanup(&self, max_size: usize) {
+        let mut cache = self.inner.write().await;
+        if cache.len() > max_size {
+            cache.clear();
+            info!("Cleared snapshot cache (exceeded {} entries)", max_size);
+        }
+    }
+}
+
+/// Content hash of a snapshot's numeric line/prices, used to detect no-op re-quotes.
+///
+/// When `line_threshold > 0` the line fields are rounded to the nearest multiple of the
+/// threshold before hashing, so a wiggle that stays inside one rounding step hashes
+/// identically. A move smaller than the threshold that crosses a rounding boundary still
+/// changes the hash; a move of at least the threshold always does.
+fn snapshot_content_hash(s: &OddsSnapshot, line_threshold: f64) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let quantize = |line: Option<f64>| -> Option<i64> {
+        line.map(|l| {
+            if line_threshold > 0.0 {
+                (l / line_threshold).round() as i64
+            } else {
+                l.to_bits() as i64
+            }
+        })
+    };
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    quantize(s.home_line).hash(&mut hasher);
+    quantize(s.away_line).hash(&mut hasher);
+    quantize(s.total_line).hash(&mut hasher);
+    s.home_price.hash(&mut hasher);
+    s.away_price.hash(&mut hasher);
+    s.over_price.hash(&mut hasher);
+    s.under_price.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A "steam move": a fast, book-correlated shift in the consensus line/total (or fair
+/// probability for h2h) detected over a short sliding window.
+#[derive(Debug, Serialize, Clone)]
+pub struct SteamAlert {
+    pub time: DateTime<Utc>,
+    pub game_id: Uuid,
+    pub external_id: String,
+    pub market_type: String,
+    pub period: String,
+    pub old_line: f64,
+    pub new_line: f64,
+    pub delta: f64,
+    pub elapsed_seconds: i64,
+    pub book_count: usize,
+    /// Books whose own line moved furthest in the same direction, leading the move.
+    pub lead_books: Vec<String>,
+    /// True when `old_line`/`new_line`/`delta` are fair probabilities (h2h), not points.
+    pub is_probability: bool,
+}
+
+/// A timestamped line observation held in a rolling window.
+#[derive(Clone, Copy)]
+struct LinePoint {
+    time: DateTime<Utc>,
+    line: f64,
+}
+
+/// Detects steam moves from a rolling per-`(game_id, market_type, period)` history of the
+/// consensus line, attributing the move to the individual books that led it.
+///
+/// A debounce map suppresses duplicate alerts for a volatile market, and a game's history
+/// is reset once it passes `commence_time`.
+#[derive(Clone)]
+pub struct SteamDetector {
+    window: chrono::Duration,
+    threshold: f64,
+    prob_threshold: f64,
+    min_books: usize,
+    debounce: chrono::Duration,
+    consensus_hist: Arc<RwLock<HashMap<String, VecDeque<LinePoint>>>>,
+    book_hist: Arc<RwLock<HashMap<String, HashMap<String, VecDeque<LinePoint>>>>>,
+    last_alert: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
+}
+
+impl SteamDetector {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            window: chrono::Duration::minutes(config.steam_window_minutes),
+            threshold: config.steam_threshold,
+            prob_threshold: config.steam_prob_threshold,
+            min_books: config.steam_min_books,
+            debounce: chrono::Duration::seconds(config.steam_debounce_seconds),
+            consensus_hist: Arc::new(RwLock::new(HashMap::new())),
+            book_hist: Arc::new(RwLock::new(HashMap::new())),
+            last_alert: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    fn key(game_id: Uuid, market_type: &str, period: &str) -> String {
+        format!("{}|{}|{}", game_id, market_type, period)
+    }
+
+    /// The numeric value a market's move is measured on: the spread/total line, or the
+    /// home fair probability for h2h.
+    fn consensus_value(c: &ConsensusSnapshot) -> Option<(f64, bool)> {
+        match c.market_type.as_str() {
+            "spreads" | "totals" => c.consensus_line.map(|l| (l, false)),
+            "h2h" => c.home_fair_prob.map(|p| (p, true)),
+            _ => None,
+        }
+    }
+
+    fn book_value(s: &OddsSnapshot) -> Option<f64> {
+        match s.market_type.as_str() {
+            "spreads" => s.home_line,
+            "totals" => s.total_line,
+            "h2h" => s.home_price.and_then(american_to_implied_prob),
+            _ => None,
+        }
+    }
+
+    /// Ingest this poll's consensus + per-book snapshots and return any steam alerts.
+    pub async fn detect(
+        &self,
+        consensus: &[ConsensusSnapshot],
+        snapshots: &[OddsSnapshot],
+        commence: &HashMap<Uuid, DateTime<Utc>>,
+    ) -> Vec<SteamAlert> {
+        let now = Utc::now();
+        let cutoff = now - self.window;
+
+        // Record per-book lines so we can attribute the move later.
+        {
+            let mut books = self.book_hist.write().await;
+            for s in snapshots {
+                if let Some(v) = Self::book_value(s) {
+                    let key = Self::key(s.game_id, &s.market_type, &s.period);
+                    let per_book = books.entry(key).or_default();
+                    let dq = per_book.entry(s.bookmaker.clone()).or_default();
+                    dq.push_back(LinePoint { time: now, line: v });
+                    while dq.front().map(|p| p.time < cutoff).unwrap_or(false) {
+                        dq.pop_front();
+                    }
+                }
+            }
+        }
+
+        let mut alerts = Vec::new();
+        let mut hist = self.consensus_hist.write().await;
+        let mut last_alert = self.last_alert.write().await;
+
+        for c in consensus {
+            let (value, is_prob) = match Self::consensus_value(c) {
+                Some(v) => v,
+                None => continue,
+            };
+            let key = Self::key(c.game_id, &c.market_type, &c.period);
+
+            // Reset history once the game has started.
+            if let Some(ct) = commence.get(&c.game_id) {
+                if now >= *ct {
+                    hist.remove(&key);
+                    last_alert.remove(&key);
+                    continue;
+                }
+            }
+
+            let dq = hist.entry(key.clone()).or_default();
+            dq.push_back(LinePoint { time: now, line: value });
+            while dq.front().map(|p| p.time < cutoff).unwrap_or(false) {
+                dq.pop_front();
+            }
+
+            // Need at least two observations in the window and enough books quoting.
+            if dq.len() < 2 || c.book_count < self.min_books {
+                continue;
+            }
+
+            let oldest = *dq.front().unwrap();
+            let delta = value - oldest.line;
+            let threshold = if is_prob { self.prob_threshold } else { self.threshold };
+            if delta.abs() < threshold {
+                continue;
+            }
+
+            // Debounce: one alert per market per debounce window.
+            if let Some(prev) = last_alert.get(&key) {
+                if now - *prev < self.debounce {
+                    continue;
+                }
+            }
+            last_alert.insert(key.clone(), now);
+
+            let lead_books = self.lead_books(c, delta).await;
+
+            alerts.push(SteamAlert {
+                time: now,
+                game_id: c.game_id,
+                external_id: c.external_id.clone(),
+                market_type: c.market_type.clone(),
+                period: c.period.clone(),
+                old_line: oldest.line,
+                new_line: value,
+                delta,
+                elapsed_seconds: (now - oldest.time).num_seconds(),
+                book_count: c.book_count,
+                lead_books,
+                is_probability: is_prob,
+            });
+        }
+
+        alerts
+    }
+
+    /// Books whose own line moved furthest in the same direction as `delta`, top 3.
+    async fn lead_books(&self, c: &ConsensusSnapshot, delta: f64) -> Vec<String> {
+        let key = Self::key(c.game_id, &c.market_type, &c.period);
+        let books = self.book_hist.read().await;
+        let Some(per_book) = books.get(&key) else {
+            return Vec::new();
+        };
+
+        let mut moves: Vec<(String, f64)> = per_book
+            .iter()
+            .filter_map(|(book, dq)| {
+                let first = dq.front()?;
+                let last = dq.back()?;
+                let m = last.line - first.line;
+                // Same direction as the consensus move and non-trivial.
+                if m.signum() == delta.signum() && m.abs() > 0.0 {
+                    Some((book.clone(), m.abs()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        moves.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        moves.into_iter().take(3).map(|(b, _)| b).collect()
+    }
+
+    /// Evict history for markets that have gone quiet, so games that drop off the feed
+    /// before their `commence_time` is ever seen don't leak entries forever (see
+    /// [`GameCache::cleanup`]/[`SnapshotCache::cleanup`]). A market is dropped once its most
+    /// recent observation is older than `max_age`.
+    pub async fn cleanup(&self, max_age: chrono::Duration) {
+        let cutoff = Utc::now() - max_age;
+        let stale = |dq: &VecDeque<LinePoint>| dq.back().map(|p| p.time < cutoff).unwrap_or(true);
+
+        {
+            let mut hist = self.consensus_hist.write().await;
+            hist.retain(|_, dq| !stale(dq));
+        }
+        {
+            let mut books = self.book_hist.write().await;
+            books.retain(|_, per_book| {
+                per_book.retain(|_, dq| !stale(dq));
+                !per_book.is_empty()
+            });
+        }
+        {
+            let mut last_alert = self.last_alert.write().await;
+            last_alert.retain(|_, t| *t >= cutoff);
+        }
+    }
+}
+
+/// Fan-out hub: a single Redis reader publishes each snapshot here and every
+/// connected SSE subscriber receives a clone via a broadcast channel.
+#[derive(Clone)]
+pub struct StreamHub {
+    tx: tokio::sync::broadcast::Sender<OddsSnapshot>,
+}
+
+impl StreamHub {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _rx) = tokio::sync::broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// Subscribe a new client to the live feed.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<OddsSnapshot> {
+        self.tx.subscribe()
+    }
+
+    /// Publish a snapshot to all subscribers. A send with no receivers is not an error.
+    pub fn publish(&self, snapshot: OddsSnapshot) {
+        let _ = self.tx.send(snapshot);
+    }
+}
+
+/// Source of raw odds-stream payloads for the fan-out reader.
+///
+/// The `Redis` variant tails the live stream with `XREAD BLOCK`; the `Mock` variant
+/// replays payloads pushed onto an in-memory channel so the reader path can be
+/// exercised without a live Redis.
+pub enum OddsStreamSource {
+    Redis {
+        conn: redis::aio::ConnectionManager,
+        stream_key: String,
+        last_id: String,
+    },
+    Mock {
+        rx: tokio::sync::mpsc::UnboundedReceiver<String>,
+    },
+}
+
+impl OddsStreamSource {
+    /// Tail a live Redis stream starting from new entries (`$`).
+    pub fn redis(conn: redis::aio::ConnectionManager, stream_key: impl Into<String>) -> Self {
+        Self::Redis { conn, stream_key: stream_key.into(), last_id: "$".to_string() }
+    }
+
+    /// In-memory source fed by `tx` — the test/offline counterpart of `redis`.
+    pub fn mock(rx: tokio::sync::mpsc::UnboundedReceiver<String>) -> Self {
+        Self::Mock { rx }
+    }
+
+    /// Block until at least one payload is available, returning the `data` field(s).
+    /// Returns an empty batch on a block timeout (Redis) or a closed channel (mock).
+    pub async fn read_next(&mut self) -> Result<Vec<String>> {
+        match self {
+            Self::Redis { conn, stream_key, last_id } => {
+                let opts = redis::streams::StreamReadOptions::default().block(5000).count(100);
+                let reply: redis::streams::StreamReadReply = conn
+                    .xread_options(&[stream_key.as_str()], &[last_id.as_str()], &opts)
+                    .await?;
+
+                let mut out = Vec::new();
+                for key in reply.keys {
+                    for entry in key.ids {
+                        last_id.clone_from(&entry.id);
+                        if let Some(v) = entry.map.get("data") {
+                            if let Ok(s) = redis::from_redis_value::<String>(v) {
+                                out.push(s);
+                            }
+                        }
+                    }
+                }
+                Ok(out)
+            }
+            Self::Mock { rx } => match rx.recv().await {
+                Some(payload) => Ok(vec![payload]),
+                None => Ok(Vec::new()),
+            },
+        }
+    }
+}
+
+/// Fan-out reader loop: pull payloads from `source`, deserialize, and broadcast.
+///
+/// A single un-parseable payload is logged and skipped; a read error backs off briefly
+/// and retries. Neither tears down the reader or disconnects other subscribers.
+pub async fn run_stream_reader(mut source: OddsStreamSource, hub: StreamHub) {
+    loop {
+        match source.read_next().await {
+            Ok(payloads) => {
+                for payload in payloads {
+                    match serde_json::from_str::<OddsSnapshot>(&payload) {
+                        Ok(snapshot) => hub.publish(snapshot),
+                        Err(e) => warn!("Skipping malformed odds stream entry: {}", e),
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Odds stream read failed: {:?}; backing off", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+/// Prometheus metrics for the ingestion service.
+///
+/// Registered against a private [`Registry`] rendered by the `/metrics` handler.
+/// Counters/histograms are `Arc`-backed internally, so cloning `Metrics` shares state.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: prometheus::Registry,
+    polls_total: prometheus::IntCounter,
+    snapshots_stored: prometheus::IntCounterVec,
+    redis_publish_failures: prometheus::IntCounter,
+    api_responses: prometheus::IntCounterVec,
+    poll_duration: prometheus::Histogram,
+    team_resolution: prometheus::IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        use prometheus::{Histogram, HistogramOpts, IntCounter, IntCounterVec, Opts, Registry};
+
+        let registry = Registry::new();
+
+        let polls_total = IntCounter::new("odds_polls_total", "Total poll iterations")?;
+        let snapshots_stored = IntCounterVec::new(
+            Opts::new("odds_snapshots_stored_total", "Snapshots stored, by market type"),
+            &["market_type"],
+        )?;
+        let redis_publish_failures = IntCounter::new(
+            "odds_redis_publish_failures_total",
+            "Redis publish failures",
+        )?;
+        let api_responses = IntCounterVec::new(
+            Opts::new("odds_api_responses_total", "The Odds API responses by status class"),
+            &["class"],
+        )?;
+        let poll_duration = Histogram::with_opts(
+            HistogramOpts::new("odds_poll_duration_seconds", "Poll duration in seconds")
+                .buckets(vec![0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0]),
+        )?;
+        let team_resolution = IntCounterVec::new(
+            Opts::new("odds_team_resolution_total", "Team resolution outcomes"),
+            &["outcome"],
+        )?;
+
+        registry.register(Box::new(polls_total.clone()))?;
+        registry.register(Box::new(snapshots_stored.clone()))?;
+        registry.register(Box::new(redis_publish_failures.clone()))?;
+        registry.register(Box::new(api_responses.clone()))?;
+        registry.register(Box::new(poll_duration.clone()))?;
+        registry.register(Box::new(team_resolution.clone()))?;
+
+        Ok(Self {
+            registry,
+            polls_total,
+            snapshots_stored,
+            redis_publish_failures,
+            api_responses,
+            poll_duration,
+            team_resolution,
+        })
+    }
+
+    /// Record an Odds API response by HTTP status class (2xx/4xx/5xx/other).
+    fn record_api_status(&self, status: reqwest::StatusCode) {
+        let class = if status.is_success() {
+            "2xx"
+        } else if status.is_client_error() {
+            "4xx"
+        } else if status.is_server_error() {
+            "5xx"
+        } else {
+            "other"
+        };
+        self.api_responses.with_label_values(&[class]).inc();
+    }
+
+    /// Render the registry in Prometheus text exposition format.
+    fn render(&self) -> Result<String> {
+        use prometheus::Encoder;
+        let encoder = prometheus::TextEncoder::new();
+        let mut buf = Vec::new();
+        encoder.encode(&self.registry.gather(), &mut buf)?;
+        Ok(String::from_utf8(buf)?)
+    }
+}
+
 /// Service health state
 #[derive(Clone)]
 pub struct HealthState {
@@ -286,8 +1106,15 @@ pub struct OddsIngestionService {
     redis: redis::aio::ConnectionManager,
     rate_limiter: RateLimiter<governor::state::NotKeyed, governor::state::InMemoryState, governor::clock::DefaultClock>,
     game_cache: GameCache,
+    snapshot_cache: SnapshotCache,
+    stream_hub: StreamHub,
+    steam_detector: SteamDetector,
     http_client: reqwest::Client,
     health: HealthState,
+    metrics: Metrics,
+    /// Whether `pg_trgm` is installed; when false, team resolution uses the regexp-only
+    /// baseline instead of `similarity()`/`%` (which would otherwise be undefined functions).
+    trgm_enabled: bool,
 }
 
 impl OddsIngestionService {
@@ -295,12 +1122,17 @@ impl OddsIngestionService {
         // Connect to database with retry
         let db = Self::connect_db_with_retry(&config.database_url, 5).await?;
 
+        // Ensure the trigram extension + indexes backing fuzzy team matching exist.
+        let trgm_enabled = Self::ensure_trgm_indexes(&db).await;
+
         // Connect to Redis with retry
         let redis = Self::connect_redis_with_retry(&config.redis_url, 5).await?;
 
         // Rate limiter: 45 requests per minute (The Odds API limit)
         let rate_limiter = RateLimiter::direct(Quota::per_minute(NonZeroU32::new(45).unwrap()));
 
+        let steam_detector = SteamDetector::from_config(&config);
+
         // HTTP client with timeouts
         let http_client = reqwest::Client::builder()
             .timeout(Duration::from_secs(30))
@@ -315,11 +1147,49 @@ impl OddsIngestionService {
             redis,
             rate_limiter,
             game_cache: GameCache::new(),
+            snapshot_cache: SnapshotCache::new(),
+            stream_hub: StreamHub::new(1024),
+            steam_detector,
             http_client,
             health: HealthState::new(),
+            metrics: Metrics::new()?,
+            trgm_enabled,
         })
     }
-    
+
+    /// Enable `pg_trgm` and create GIN trigram indexes on the names we match against,
+    /// returning whether the extension is actually present afterwards.
+    ///
+    /// Best-effort: a managed role may lack `CREATE EXTENSION` rights, in which case we log
+    /// and carry on. When the extension is absent `similarity()`/`%` are undefined, so the
+    /// returned flag steers [`resolve_team_fuzzy`] onto its regexp-only baseline instead of
+    /// erroring every `get_or_create_team` call.
+    async fn ensure_trgm_indexes(db: &PgPool) -> bool {
+        let statements = [
+            "CREATE EXTENSION IF NOT EXISTS pg_trgm",
+            "CREATE INDEX IF NOT EXISTS idx_teams_canonical_name_trgm \
+             ON teams USING gin (lower(canonical_name) gin_trgm_ops)",
+            "CREATE INDEX IF NOT EXISTS idx_team_aliases_alias_trgm \
+             ON team_aliases USING gin (lower(alias) gin_trgm_ops)",
+        ];
+        for stmt in statements {
+            if let Err(e) = sqlx::query(stmt).execute(db).await {
+                warn!("Trigram setup step failed ({}): {}", stmt, e);
+            }
+        }
+
+        let enabled: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM pg_extension WHERE extname = 'pg_trgm')"
+        )
+        .fetch_one(db)
+        .await
+        .unwrap_or(false);
+        if !enabled {
+            warn!("pg_trgm not available; team resolution falls back to exact-strip matching");
+        }
+        enabled
+    }
+
     async fn connect_db_with_retry(url: &str, max_retries: u32) -> Result<PgPool> {
         let mut attempt = 0;
         loop {
@@ -411,6 +1281,7 @@ impl OddsIngestionService {
         }
 
         let status = response.status();
+        self.metrics.record_api_status(status);
         let body = response
             .text()
             .await
@@ -431,32 +1302,131 @@ impl OddsIngestionService {
         Ok(events)
     }
 
-    /// Fetch first-half odds for a specific event using the event odds endpoint
-    /// Premium subscription required for alternate/period markets
-    pub async fn fetch_event_h1_odds(&self, event_id: &str) -> Result<Option<OddsApiEvent>> {
+    /// Fetch a historical snapshot of all events as of `date` from the historical endpoint.
+    ///
+    /// Returns the events along with the actual snapshot timestamp reported by the API
+    /// (which may differ slightly from the requested `date`). Surfaces the remaining-quota
+    /// header so a long backfill can be throttled before it blows the budget.
+    pub async fn fetch_historical_events(
+        &self,
+        date: DateTime<Utc>,
+    ) -> Result<(DateTime<Utc>, Vec<OddsApiEvent>)> {
         // Wait for rate limit
         self.rate_limiter.until_ready().await;
 
         let url = format!(
-            "https://api.the-odds-api.com/v4/sports/{}/events/{}/odds",
-            self.config.sport_key,
-            event_id
+            "https://api.the-odds-api.com/v4/historical/sports/{}/odds",
+            self.config.sport_key
         );
 
-        // For 1H markets, include Bovada as they provide best NCAAB 1H coverage
-        // Keep pinnacle/circa/bookmaker for consistency where available
         let response = self.http_client
             .get(&url)
             .query(&[
                 ("apiKey", &self.config.odds_api_key),
                 ("regions", &"us".to_string()),
-                ("markets", &"spreads_h1,totals_h1,h2h_h1".to_string()),
-                ("bookmakers", &"bovada,pinnacle,circa,bookmaker".to_string()),
+                ("markets", &"spreads,totals,h2h".to_string()),
                 ("oddsFormat", &"american".to_string()),
+                ("date", &date.to_rfc3339()),
             ])
             .send()
             .await
-            .context("Failed to fetch event H1 odds")?;
+            .context("Failed to fetch historical events")?;
+
+        if let Some(remaining) = response.headers().get("x-requests-remaining") {
+            info!("API requests remaining: {}", remaining.to_str().unwrap_or("?"));
+        }
+
+        let status = response.status();
+        self.metrics.record_api_status(status);
+        let body = response.text().await.context("Failed to read historical response body")?;
+
+        if !status.is_success() {
+            return Err(anyhow!("Odds API historical error (status {}): {}", status, body));
+        }
+
+        let snapshot: HistoricalSnapshot = serde_json::from_str(&body)
+            .context("Failed to parse historical snapshot")?;
+
+        let ts = snapshot.timestamp.unwrap_or(date);
+        info!("Fetched {} historical events as of {}", snapshot.data.len(), ts.to_rfc3339());
+        Ok((ts, snapshot.data))
+    }
+
+    /// Drive the historical endpoint across the configured backfill window and exit.
+    ///
+    /// Steps between `backfill_from` and `backfill_to` by `backfill_step_seconds` (in
+    /// whichever direction the window points), reusing `process_events_at`/`get_or_create_game`
+    /// and the normal storage path so historical rows land in the same tables with their
+    /// real `time`.
+    pub async fn run_backfill(&self) -> Result<()> {
+        let from = self.config.backfill_from
+            .ok_or_else(|| anyhow!("run_backfill called without BACKFILL_FROM"))?;
+        let to = self.config.backfill_to.unwrap_or_else(Utc::now);
+        let step = chrono::Duration::seconds(self.config.backfill_step_seconds.max(1) as i64);
+        // Step toward `to`, regardless of which side of the window it's on.
+        let step = if to < from { -step } else { step };
+
+        info!(
+            "Starting backfill from {} to {} (step {}s)",
+            from.to_rfc3339(), to.to_rfc3339(), self.config.backfill_step_seconds
+        );
+
+        let mut cursor = from;
+        let mut total = 0usize;
+        loop {
+            let done = if step < chrono::Duration::zero() { cursor < to } else { cursor > to };
+            if done {
+                break;
+            }
+
+            match self.fetch_historical_events(cursor).await {
+                Ok((ts, events)) => {
+                    let processed = self.process_events_at(events, ts, false).await?;
+                    let consensus = self.compute_consensus(&processed.all);
+                    self.store_snapshots(&processed.fresh).await?;
+                    self.publish_to_redis(&processed.fresh).await?;
+                    self.publish_consensus_to_redis(&consensus).await?;
+                    total += processed.fresh.len();
+                    info!("Backfill {}: {} snapshots ({} total)", ts.to_rfc3339(), processed.fresh.len(), total);
+                }
+                Err(e) => {
+                    warn!("Backfill step {} failed: {:?}", cursor.to_rfc3339(), e);
+                }
+            }
+
+            cursor += step;
+        }
+
+        info!("Backfill complete: {} snapshots stored", total);
+        Ok(())
+    }
+
+    /// Fetch first-half odds for a specific event using the event odds endpoint
+    /// Premium subscription required for alternate/period markets
+    pub async fn fetch_event_h1_odds(&self, event_id: &str) -> Result<Option<OddsApiEvent>> {
+        // Wait for rate limit
+        self.rate_limiter.until_ready().await;
+
+        let url = format!(
+            "https://api.the-odds-api.com/v4/sports/{}/events/{}/odds",
+            self.config.sport_key,
+            event_id
+        );
+
+        // For 1H markets, include Bovada as they provide best NCAAB 1H coverage
+        // Keep pinnacle/circa/bookmaker for consistency where available
+        let response = self.http_client
+            .get(&url)
+            .query(&[
+                ("apiKey", &self.config.odds_api_key),
+                ("regions", &"us".to_string()),
+                ("markets", &"spreads_h1,totals_h1,h2h_h1".to_string()),
+                ("bookmakers", &"bovada,pinnacle,circa,bookmaker".to_string()),
+                ("oddsFormat", &"american".to_string()),
+            ])
+            .send()
+            .await
+            .context("Failed to fetch event H1 odds")?;
 
         let status = response.status();
         let body = response
@@ -562,10 +1532,22 @@ impl OddsIngestionService {
         Ok(h2_events)
     }
 
-    /// Process events and extract odds
-    pub async fn process_events(&self, events: Vec<OddsApiEvent>) -> Result<Vec<OddsSnapshot>> {
-        let mut snapshots = Vec::new();
-        let now = Utc::now();
+    /// Process events and extract odds, stamping snapshots with the current time.
+    ///
+    /// Applies the staleness/out-of-order guard so unchanged markets aren't re-emitted.
+    pub async fn process_events(&self, events: Vec<OddsApiEvent>) -> Result<ProcessedSnapshots> {
+        self.process_events_at(events, Utc::now(), true).await
+    }
+
+    /// Process events and extract odds, stamping every snapshot with `time`.
+    ///
+    /// Live polling passes `Utc::now()` with `dedup = true`; the historical backfill passes
+    /// the real snapshot timestamp with `dedup = false` so every historical pull lands in the
+    /// same tables with its true `time` (the guard's newest-wins logic would otherwise drop a
+    /// backward walk).
+    pub async fn process_events_at(&self, events: Vec<OddsApiEvent>, now: DateTime<Utc>, dedup: bool) -> Result<ProcessedSnapshots> {
+        let mut all = Vec::new();
+        let mut fresh = Vec::new();
 
         for event in events {
             // Get or create game ID using race-condition-free cache
@@ -586,13 +1568,33 @@ impl OddsIngestionService {
                     );
 
                     if let Some(s) = snapshot {
-                        snapshots.push(s);
+                        // Every extracted market feeds consensus/steam detection; the dedup
+                        // gate only decides what gets stored and published to `odds.live`.
+                        let is_fresh = if dedup {
+                            let hash = snapshot_content_hash(&s, self.config.dedup_line_threshold);
+                            self.snapshot_cache.observe(
+                                s.game_id,
+                                &s.bookmaker,
+                                &s.market_type,
+                                &s.period,
+                                market.last_update,
+                                hash,
+                                now,
+                                chrono::Duration::seconds(self.config.forced_flush_seconds),
+                            ).await
+                        } else {
+                            true
+                        };
+                        if is_fresh {
+                            fresh.push(s.clone());
+                        }
+                        all.push(s);
                     }
                 }
             }
         }
 
-        Ok(snapshots)
+        Ok(ProcessedSnapshots { all, fresh })
     }
 
     /// Extract odds snapshot from a market
@@ -632,6 +1634,11 @@ impl OddsIngestionService {
             away_price: None,
             over_price: None,
             under_price: None,
+            home_fair_prob: None,
+            away_fair_prob: None,
+            over_fair_prob: None,
+            under_fair_prob: None,
+            overround: None,
         };
 
         for outcome in &market.outcomes {
@@ -664,6 +1671,27 @@ impl OddsIngestionService {
             }
         }
 
+        // Remove the bookmaker margin so downstream modeling gets a fair line. Spreads/h2h
+        // de-vig the home/away pair; totals de-vig the over/under pair. One-sided markets
+        // stay NULL.
+        match market_type {
+            "spreads" | "h2h" => {
+                if let Some((fa, fb, overround)) = devig_two_way(snapshot.home_price, snapshot.away_price) {
+                    snapshot.home_fair_prob = Some(fa);
+                    snapshot.away_fair_prob = Some(fb);
+                    snapshot.overround = Some(overround);
+                }
+            }
+            "totals" => {
+                if let Some((fo, fu, overround)) = devig_two_way(snapshot.over_price, snapshot.under_price) {
+                    snapshot.over_fair_prob = Some(fo);
+                    snapshot.under_fair_prob = Some(fu);
+                    snapshot.overround = Some(overround);
+                }
+            }
+            _ => {}
+        }
+
         Some(snapshot)
     }
 
@@ -800,6 +1828,7 @@ impl OddsIngestionService {
                         .execute(&self.db)
                         .await?;
                     }
+                    self.metrics.team_resolution.with_label_values(&["resolved_with_ratings"]).inc();
                     return Ok(id);
                 }
 
@@ -825,6 +1854,7 @@ impl OddsIngestionService {
                 .execute(&self.db)
                 .await?;
             }
+            self.metrics.team_resolution.with_label_values(&["resolved_unrated"]).inc();
             return Ok(id);
         }
 
@@ -870,6 +1900,7 @@ impl OddsIngestionService {
             .execute(&self.db)
             .await?;
 
+        self.metrics.team_resolution.with_label_values(&["newly_created"]).inc();
         Ok(final_team_id)
     }
 
@@ -996,9 +2027,72 @@ impl OddsIngestionService {
         Some(format!("{} {}", expanded, rest.join(" ")))
     }
 
-    /// Fuzzy resolution that ignores punctuation/spacing and checks both canonical names
-    /// and aliases, preferring teams that already have ratings.
+    /// Fuzzy resolution over canonical names and aliases, preferring teams that already
+    /// have ratings.
+    ///
+    /// When `pg_trgm` is available this runs an index-accelerated similarity search and
+    /// accepts the best candidate clearing `config.team_match_threshold`; otherwise (or when
+    /// that finds nothing) it falls back to the exact punctuation/spacing-stripped lookup,
+    /// which also serves as a guard for distinctive short names scoring below the threshold.
+    /// Returning `None` lets the caller create a new canonical row, exactly as before.
     async fn resolve_team_fuzzy(&self, input: &str) -> Result<Option<(Uuid, String, bool)>> {
+        if self.trgm_enabled {
+            if let Some(hit) = self.resolve_team_trgm(input).await? {
+                return Ok(Some(hit));
+            }
+        }
+        self.resolve_team_exact(input).await
+    }
+
+    /// Trigram similarity search that hits the `gin_trgm_ops` indexes via the `%` operator.
+    ///
+    /// `SET LOCAL pg_trgm.similarity_threshold` pins the `%` cutoff to our configured
+    /// threshold for this transaction so the index prefilter and the `sim` ranking agree.
+    async fn resolve_team_trgm(&self, input: &str) -> Result<Option<(Uuid, String, bool)>> {
+        let threshold = self.config.team_match_threshold;
+
+        let mut tx = self.db.begin().await?;
+        // `threshold` is an f64 from config, not user input, so formatting it in is safe.
+        sqlx::query(&format!("SET LOCAL pg_trgm.similarity_threshold = {}", threshold))
+            .execute(&mut *tx)
+            .await?;
+
+        let resolved: Option<(Uuid, String, bool)> = sqlx::query_as(
+            r#"
+            SELECT id, canonical_name, has_ratings
+            FROM (
+                SELECT
+                  t.id,
+                  t.canonical_name,
+                  EXISTS(SELECT 1 FROM team_ratings tr WHERE tr.team_id = t.id) AS has_ratings,
+                  GREATEST(
+                      similarity(lower(t.canonical_name), lower($1)),
+                      COALESCE(MAX(similarity(lower(ta.alias), lower($1))), 0)
+                  ) AS sim
+                FROM teams t
+                LEFT JOIN team_aliases ta ON t.id = ta.team_id
+                WHERE lower(t.canonical_name) % lower($1)
+                   OR lower(ta.alias) % lower($1)
+                GROUP BY t.id, t.canonical_name
+            ) cand
+            WHERE cand.sim >= $2
+            ORDER BY cand.has_ratings DESC, cand.sim DESC, cand.canonical_name
+            LIMIT 1
+            "#
+        )
+        .bind(input)
+        .bind(threshold)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(resolved)
+    }
+
+    /// Baseline resolution: match when the non-alphanumeric-stripped canonical/alias equals
+    /// the stripped input, preferring rated teams. Used when `pg_trgm` is absent and as the
+    /// trigram path's guard/fallback.
+    async fn resolve_team_exact(&self, input: &str) -> Result<Option<(Uuid, String, bool)>> {
         let resolved: Option<(Uuid, String, bool)> = sqlx::query_as(
             r#"
             SELECT
@@ -1022,6 +2116,143 @@ impl OddsIngestionService {
         Ok(resolved)
     }
 
+    /// Bind a raw feed name to a chosen canonical team id and mark its audit rows resolved.
+    ///
+    /// Reuses the `(alias, source)` conflict target, but repoints an existing alias to the
+    /// corrected team rather than ignoring it, so a bad resolution can be fixed in place.
+    pub async fn upsert_alias(&self, team_id: Uuid, alias: &str, source: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO team_aliases (team_id, alias, source)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (alias, source) DO UPDATE SET team_id = EXCLUDED.team_id
+            "#
+        )
+        .bind(team_id)
+        .bind(alias)
+        .bind(source)
+        .execute(&self.db)
+        .await?;
+
+        sqlx::query("UPDATE team_resolution_audit SET resolved = true WHERE input_name = $1")
+            .bind(alias)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Repoint games and aliases from a duplicate team row onto the correct one, then drop
+    /// the duplicate. Aliases that would collide on `(alias, source)` are discarded.
+    pub async fn merge_teams(&self, from_team_id: Uuid, to_team_id: Uuid) -> Result<()> {
+        if from_team_id == to_team_id {
+            return Err(anyhow!("cannot merge a team into itself"));
+        }
+
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query("UPDATE games SET home_team_id = $2 WHERE home_team_id = $1")
+            .bind(from_team_id)
+            .bind(to_team_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE games SET away_team_id = $2 WHERE away_team_id = $1")
+            .bind(from_team_id)
+            .bind(to_team_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // Drop aliases that already exist on the target to avoid the unique conflict.
+        sqlx::query(
+            r#"
+            DELETE FROM team_aliases a
+            WHERE a.team_id = $1
+              AND EXISTS (
+                  SELECT 1 FROM team_aliases b
+                  WHERE b.team_id = $2 AND b.alias = a.alias AND b.source = a.source
+              )
+            "#
+        )
+        .bind(from_team_id)
+        .bind(to_team_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE team_aliases SET team_id = $2 WHERE team_id = $1")
+            .bind(from_team_id)
+            .bind(to_team_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // Repoint the duplicate's ratings onto the target, keeping the target's own row when
+        // it already has ratings. Left behind, these would FK-reference the about-to-be-deleted
+        // team and abort the merge whenever the duplicate carried ratings.
+        sqlx::query(
+            r#"
+            DELETE FROM team_ratings r
+            WHERE r.team_id = $1
+              AND EXISTS (SELECT 1 FROM team_ratings t WHERE t.team_id = $2)
+            "#
+        )
+        .bind(from_team_id)
+        .bind(to_team_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE team_ratings SET team_id = $2 WHERE team_id = $1")
+            .bind(from_team_id)
+            .bind(to_team_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM teams WHERE id = $1")
+            .bind(from_team_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        info!("Merged team {} into {}", from_team_id, to_team_id);
+        Ok(())
+    }
+
+    /// Inject or correct a game's team assignment. Mirrors `get_or_create_game`'s upsert so
+    /// the fix takes effect on the next poll, and refreshes the in-memory game cache.
+    pub async fn upsert_game(
+        &self,
+        external_id: &str,
+        home_team_id: Uuid,
+        away_team_id: Uuid,
+        commence_time: Option<DateTime<Utc>>,
+    ) -> Result<Uuid> {
+        if home_team_id == away_team_id {
+            return Err(anyhow!("home and away team ids must differ"));
+        }
+
+        let game_id = Uuid::new_v4();
+        let (final_game_id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO games (id, external_id, home_team_id, away_team_id, commence_time, status)
+            VALUES ($1, $2, $3, $4, $5, 'scheduled')
+            ON CONFLICT (external_id) DO UPDATE SET
+                home_team_id = EXCLUDED.home_team_id,
+                away_team_id = EXCLUDED.away_team_id,
+                commence_time = EXCLUDED.commence_time
+            RETURNING id
+            "#
+        )
+        .bind(game_id)
+        .bind(external_id)
+        .bind(home_team_id)
+        .bind(away_team_id)
+        .bind(commence_time.unwrap_or_else(Utc::now))
+        .fetch_one(&self.db)
+        .await?;
+
+        self.game_cache.insert(external_id.to_string(), final_game_id).await;
+        info!("Upserted game {} ({})", external_id, final_game_id);
+        Ok(final_game_id)
+    }
+
     /// Store snapshots in TimescaleDB
     pub async fn store_snapshots(&self, snapshots: &[OddsSnapshot]) -> Result<()> {
         if snapshots.is_empty() {
@@ -1036,8 +2267,9 @@ impl OddsIngestionService {
                 INSERT INTO odds_snapshots (
                     time, game_id, bookmaker, market_type, period,
                     home_line, away_line, total_line,
-                    home_price, away_price, over_price, under_price
-                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                    home_price, away_price, over_price, under_price,
+                    home_fair_prob, away_fair_prob, over_fair_prob, under_fair_prob, overround
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
                 ON CONFLICT (time, game_id, bookmaker, market_type, period) DO UPDATE SET
                     home_line = EXCLUDED.home_line,
                     away_line = EXCLUDED.away_line,
@@ -1045,7 +2277,12 @@ impl OddsIngestionService {
                     home_price = EXCLUDED.home_price,
                     away_price = EXCLUDED.away_price,
                     over_price = EXCLUDED.over_price,
-                    under_price = EXCLUDED.under_price
+                    under_price = EXCLUDED.under_price,
+                    home_fair_prob = EXCLUDED.home_fair_prob,
+                    away_fair_prob = EXCLUDED.away_fair_prob,
+                    over_fair_prob = EXCLUDED.over_fair_prob,
+                    under_fair_prob = EXCLUDED.under_fair_prob,
+                    overround = EXCLUDED.overround
                 "#
             )
                 .bind(snapshot.time)
@@ -1060,15 +2297,161 @@ impl OddsIngestionService {
                 .bind(snapshot.away_price)
                 .bind(snapshot.over_price)
                 .bind(snapshot.under_price)
+                .bind(snapshot.home_fair_prob)
+                .bind(snapshot.away_fair_prob)
+                .bind(snapshot.over_fair_prob)
+                .bind(snapshot.under_fair_prob)
+                .bind(snapshot.overround)
                 .execute(&mut *tx)
                 .await?;
         }
 
         tx.commit().await?;
+
+        for snapshot in snapshots {
+            self.metrics
+                .snapshots_stored
+                .with_label_values(&[snapshot.market_type.as_str()])
+                .inc();
+        }
+
         info!("Stored {} odds snapshots", snapshots.len());
         Ok(())
     }
 
+    /// Relative weight given to a book when forming a consensus.
+    /// Sharp books (Pinnacle, Circa) count more than recreational books.
+    fn book_weight(bookmaker: &str) -> u32 {
+        match bookmaker.to_lowercase().as_str() {
+            "pinnacle" | "circa" => 3,
+            "bookmaker" => 2,
+            _ => 1,
+        }
+    }
+
+    /// Build sharp-weighted consensus snapshots from a set of per-book snapshots.
+    ///
+    /// Snapshots are grouped by `(game_id, market_type, period)`; each book's market is
+    /// de-vigged and the fair probabilities and line are reduced to a weighted median.
+    /// Markets that can't be de-vigged (missing a side) contribute nothing.
+    pub fn compute_consensus(&self, snapshots: &[OddsSnapshot]) -> Vec<ConsensusSnapshot> {
+        // group key -> (external_id, per-book fair data)
+        let mut groups: HashMap<(Uuid, String, String), (String, Vec<ConsensusRow>)> = HashMap::new();
+
+        for s in snapshots {
+            // h2h has no line; spreads use home_line, totals use total_line.
+            let (price_a, price_b, line) = match s.market_type.as_str() {
+                "spreads" => (s.home_price, s.away_price, s.home_line),
+                "totals" => (s.over_price, s.under_price, s.total_line),
+                "h2h" => (s.home_price, s.away_price, None),
+                _ => continue,
+            };
+
+            if let Some((fa, fb, _overround)) = devig_two_way(price_a, price_b) {
+                let entry = groups
+                    .entry((s.game_id, s.market_type.clone(), s.period.clone()))
+                    .or_insert_with(|| (s.external_id.clone(), Vec::new()));
+                entry.1.push(ConsensusRow {
+                    weight: Self::book_weight(&s.bookmaker),
+                    fair_a: fa,
+                    fair_b: fb,
+                    line,
+                });
+            }
+        }
+
+        let now = Utc::now();
+        let mut out = Vec::with_capacity(groups.len());
+        for ((game_id, market_type, period), (external_id, rows)) in groups {
+            if rows.is_empty() {
+                continue;
+            }
+            let book_count = rows.len();
+            let home_fair_prob = weighted_median(
+                rows.iter().map(|r| (r.fair_a, r.weight)),
+            );
+            let away_fair_prob = weighted_median(
+                rows.iter().map(|r| (r.fair_b, r.weight)),
+            );
+            let consensus_line = weighted_median(
+                rows.iter().filter_map(|r| r.line.map(|l| (l, r.weight))),
+            );
+
+            out.push(ConsensusSnapshot {
+                time: now,
+                game_id,
+                external_id,
+                market_type,
+                period,
+                consensus_line,
+                home_fair_prob,
+                away_fair_prob,
+                home_fair_price: home_fair_prob.and_then(implied_prob_to_american),
+                away_fair_price: away_fair_prob.and_then(implied_prob_to_american),
+                book_count,
+            });
+        }
+
+        out.sort_by(|a, b| (a.game_id, &a.market_type, &a.period).cmp(&(b.game_id, &b.market_type, &b.period)));
+        out
+    }
+
+    /// Publish consensus snapshots to a dedicated Redis stream.
+    pub async fn publish_consensus_to_redis(&self, consensus: &[ConsensusSnapshot]) -> Result<()> {
+        if consensus.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.redis.clone();
+        for c in consensus {
+            let payload = serde_json::to_string(c)?;
+            let _: String = conn.xadd(
+                "odds.consensus",
+                "*",
+                &[
+                    ("game_id", c.game_id.to_string()),
+                    ("market_type", c.market_type.clone()),
+                    ("period", c.period.clone()),
+                    ("data", payload),
+                ],
+            ).await?;
+        }
+
+        info!("Published {} consensus snapshots to Redis", consensus.len());
+        Ok(())
+    }
+
+    /// Publish steam-move alerts to a dedicated Redis stream.
+    pub async fn publish_steam_alerts(&self, alerts: &[SteamAlert]) -> Result<()> {
+        if alerts.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.redis.clone();
+        for alert in alerts {
+            let payload = serde_json::to_string(alert)?;
+            let _: String = conn.xadd(
+                "odds.steam",
+                "*",
+                &[
+                    ("game_id", alert.game_id.to_string()),
+                    ("market_type", alert.market_type.clone()),
+                    ("period", alert.period.clone()),
+                    ("data", payload),
+                ],
+            ).await?;
+
+            info!(
+                "STEAM {} {} {}: {} -> {} (Δ{:+.2} in {}s, {} books, led by {:?})",
+                alert.external_id, alert.market_type, alert.period,
+                alert.old_line, alert.new_line, alert.delta,
+                alert.elapsed_seconds, alert.book_count, alert.lead_books,
+            );
+        }
+
+        Ok(())
+    }
+
     /// Publish snapshots to Redis Stream
     pub async fn publish_to_redis(&self, snapshots: &[OddsSnapshot]) -> Result<()> {
         if snapshots.is_empty() {
@@ -1080,7 +2463,7 @@ impl OddsIngestionService {
         for snapshot in snapshots {
             let payload = serde_json::to_string(snapshot)?;
 
-            let _: String = conn.xadd(
+            let result: redis::RedisResult<String> = conn.xadd(
                 "odds.live",
                 "*",
                 &[
@@ -1089,15 +2472,284 @@ impl OddsIngestionService {
                     ("market_type", snapshot.market_type.clone()),
                     ("data", payload),
                 ],
-            ).await?;
+            ).await;
+
+            if let Err(e) = result {
+                self.metrics.redis_publish_failures.inc();
+                return Err(e).context("Failed to publish snapshot to Redis");
+            }
         }
 
         info!("Published {} snapshots to Redis", snapshots.len());
         Ok(())
     }
 
+    /// Aggregate recent snapshots into the `odds_candles` hypertable.
+    ///
+    /// Runs periodically (see `run`) and upserts one OHLC row per
+    /// `(bucket, game_id, bookmaker, market_type, period)` over the given lookback.
+    /// TimescaleDB's `time_bucket` does the windowing; a window with a single snapshot
+    /// naturally collapses to `open == high == low == close`.
+    pub async fn aggregate_candles(&self, lookback: Duration) -> Result<usize> {
+        let bucket = format!("{} seconds", self.config.candle_bucket_seconds);
+        let since = Utc::now() - chrono::Duration::from_std(lookback)?;
+
+        let rows: Vec<(DateTime<Utc>, Uuid, String, String, String,
+                        Option<f64>, Option<f64>, Option<f64>, Option<f64>,
+                        Option<i32>, Option<i32>)> = sqlx::query_as(
+            r#"
+            WITH bucketed AS (
+                SELECT
+                    time_bucket($1::interval, time) AS bucket,
+                    game_id, bookmaker, market_type, period,
+                    time,
+                    CASE WHEN market_type = 'totals' THEN total_line ELSE home_line END AS line,
+                    CASE WHEN market_type = 'totals' THEN over_price ELSE home_price END AS price
+                FROM odds_snapshots
+                WHERE time >= $2
+            )
+            SELECT
+                bucket, game_id, bookmaker, market_type, period,
+                first(line, time)  AS open_line,
+                max(line)          AS high_line,
+                min(line)          AS low_line,
+                last(line, time)   AS close_line,
+                first(price, time) AS open_price,
+                last(price, time)  AS close_price
+            FROM bucketed
+            GROUP BY bucket, game_id, bookmaker, market_type, period
+            ORDER BY bucket
+            "#
+        )
+        .bind(&bucket)
+        .bind(since)
+        .fetch_all(&self.db)
+        .await?;
+
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self.db.begin().await?;
+        for (bucket_ts, game_id, bookmaker, market_type, period,
+             open_line, high_line, low_line, close_line, open_price, close_price) in &rows {
+            sqlx::query(
+                r#"
+                INSERT INTO odds_candles (
+                    bucket, game_id, bookmaker, market_type, period,
+                    open_line, high_line, low_line, close_line, open_price, close_price
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (bucket, game_id, bookmaker, market_type, period) DO UPDATE SET
+                    open_line = EXCLUDED.open_line,
+                    high_line = EXCLUDED.high_line,
+                    low_line = EXCLUDED.low_line,
+                    close_line = EXCLUDED.close_line,
+                    open_price = EXCLUDED.open_price,
+                    close_price = EXCLUDED.close_price
+                "#
+            )
+                .bind(bucket_ts)
+                .bind(game_id)
+                .bind(bookmaker)
+                .bind(market_type)
+                .bind(period)
+                .bind(open_line)
+                .bind(high_line)
+                .bind(low_line)
+                .bind(close_line)
+                .bind(open_price)
+                .bind(close_price)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        info!("Aggregated {} odds candles ({} buckets)", rows.len(), bucket);
+        Ok(rows.len())
+    }
+
+    /// Read back a gap-filled candle series for a single game/market.
+    ///
+    /// Buckets missing from `odds_candles` are synthesized by carrying the previous
+    /// close forward (flagged `gap_filled`) so a line chart has no holes. The gap-fill step
+    /// is always `config.candle_bucket_seconds` — the width the candles were aggregated at —
+    /// so it can't drift from the stored buckets and fabricate or skip windows.
+    pub async fn query_candles(
+        &self,
+        game_id: Uuid,
+        market_type: &str,
+        period: &str,
+    ) -> Result<Vec<OddsCandle>> {
+        let rows: Vec<(DateTime<Utc>, String,
+                        Option<f64>, Option<f64>, Option<f64>, Option<f64>,
+                        Option<i32>, Option<i32>)> = sqlx::query_as(
+            r#"
+            SELECT
+                bucket, bookmaker,
+                open_line, high_line, low_line, close_line, open_price, close_price
+            FROM odds_candles
+            WHERE game_id = $1 AND market_type = $2 AND period = $3
+            ORDER BY bookmaker, bucket
+            "#
+        )
+        .bind(game_id)
+        .bind(market_type)
+        .bind(period)
+        .fetch_all(&self.db)
+        .await?;
+
+        let step = chrono::Duration::seconds(self.config.candle_bucket_seconds as i64);
+        let mut out: Vec<OddsCandle> = Vec::new();
+
+        // Gap-fill per bookmaker: walk each book's buckets in order and carry the
+        // previous close across any empty windows in between.
+        let mut by_book: HashMap<String, Vec<(DateTime<Utc>, Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<i32>, Option<i32>)>> = HashMap::new();
+        for (b, book, ol, hl, ll, cl, op, cp) in rows {
+            by_book.entry(book).or_default().push((b, ol, hl, ll, cl, op, cp));
+        }
+
+        for (book, series) in by_book {
+            let mut prev_close: Option<f64> = None;
+            let mut prev_price: Option<i32> = None;
+            let mut expected: Option<DateTime<Utc>> = None;
+
+            for (b, ol, hl, ll, cl, op, cp) in series {
+                // Emit carried-forward candles for any skipped windows.
+                if let Some(mut exp) = expected {
+                    while exp < b {
+                        out.push(OddsCandle {
+                            bucket: exp,
+                            game_id,
+                            bookmaker: book.clone(),
+                            market_type: market_type.to_string(),
+                            period: period.to_string(),
+                            open_line: prev_close,
+                            high_line: prev_close,
+                            low_line: prev_close,
+                            close_line: prev_close,
+                            open_price: prev_price,
+                            close_price: prev_price,
+                            gap_filled: true,
+                        });
+                        exp += step;
+                    }
+                }
+
+                out.push(OddsCandle {
+                    bucket: b,
+                    game_id,
+                    bookmaker: book.clone(),
+                    market_type: market_type.to_string(),
+                    period: period.to_string(),
+                    open_line: ol,
+                    high_line: hl,
+                    low_line: ll,
+                    close_line: cl,
+                    open_price: op,
+                    close_price: cp,
+                    gap_filled: false,
+                });
+
+                prev_close = cl;
+                prev_price = cp;
+                expected = Some(b + step);
+            }
+        }
+
+        out.sort_by(|a, b| (&a.bookmaker, a.bucket).cmp(&(&b.bookmaker, b.bucket)));
+        Ok(out)
+    }
+
+    /// Read back a game's line-movement history, one series per bookmaker/market/period.
+    ///
+    /// Snapshots are down-sampled with TimescaleDB's `time_bucket` (the configured candle
+    /// width) so repeated identical polls collapse to one point per window, keeping the
+    /// `last` line/price in each. Each series also carries its open line, current line, and
+    /// net movement. `bookmaker`/`market_type` filter the series and `since` bounds the
+    /// window; all three are optional.
+    pub async fn query_line_history(
+        &self,
+        game_id: Uuid,
+        bookmaker: Option<&str>,
+        market_type: Option<&str>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LineHistorySeries>> {
+        let bucket = format!("{} seconds", self.config.candle_bucket_seconds);
+
+        let rows: Vec<(String, String, String, DateTime<Utc>, Option<f64>, Option<i32>)> =
+            sqlx::query_as(
+                r#"
+                WITH bucketed AS (
+                    SELECT
+                        bookmaker, market_type, period,
+                        time_bucket($2::interval, time) AS bucket,
+                        time,
+                        CASE WHEN market_type = 'totals' THEN total_line ELSE home_line END AS line,
+                        CASE WHEN market_type = 'totals' THEN over_price ELSE home_price END AS price
+                    FROM odds_snapshots
+                    WHERE game_id = $1
+                      AND ($3::text IS NULL OR bookmaker = $3)
+                      AND ($4::text IS NULL OR market_type = $4)
+                      AND ($5::timestamptz IS NULL OR time >= $5)
+                )
+                SELECT
+                    bookmaker, market_type, period, bucket,
+                    last(line, time)  AS line,
+                    last(price, time) AS price
+                FROM bucketed
+                GROUP BY bookmaker, market_type, period, bucket
+                ORDER BY bookmaker, market_type, period, bucket
+                "#
+            )
+            .bind(game_id)
+            .bind(&bucket)
+            .bind(bookmaker)
+            .bind(market_type)
+            .bind(since)
+            .fetch_all(&self.db)
+            .await?;
+
+        // Rows arrive grouped by (bookmaker, market_type, period) and ordered by bucket, so
+        // we fold consecutive runs into one series each.
+        let mut out: Vec<LineHistorySeries> = Vec::new();
+        for (book, market, period, bucket_ts, line, price) in rows {
+            let same_series = out
+                .last()
+                .map(|s| s.bookmaker == book && s.market_type == market && s.period == period)
+                .unwrap_or(false);
+            if !same_series {
+                out.push(LineHistorySeries {
+                    bookmaker: book,
+                    market_type: market,
+                    period,
+                    open_line: None,
+                    current_line: None,
+                    net_movement: None,
+                    points: Vec::new(),
+                });
+            }
+            out.last_mut().unwrap().points.push(LineHistoryPoint {
+                time: bucket_ts,
+                line,
+                price,
+            });
+        }
+
+        // Derive open/current/net from the ordered points of each series.
+        for series in &mut out {
+            series.open_line = series.points.first().and_then(|p| p.line);
+            series.current_line = series.points.last().and_then(|p| p.line);
+            series.net_movement = match (series.open_line, series.current_line) {
+                (Some(open), Some(current)) => Some(current - open),
+                _ => None,
+            };
+        }
+
+        Ok(out)
+    }
+
     /// Main polling loop
-    pub async fn run(&self) -> Result<()> {
+    pub async fn run(self: Arc<Self>) -> Result<()> {
         info!(
             "Starting odds ingestion loop (poll interval: {}s)",
             self.config.poll_interval_seconds
@@ -1105,10 +2757,31 @@ impl OddsIngestionService {
 
         // Periodic cache cleanup
         let game_cache = self.game_cache.clone();
+        let snapshot_cache = self.snapshot_cache.clone();
+        let steam_detector = self.steam_detector.clone();
         tokio::spawn(async move {
             loop {
                 tokio::time::sleep(Duration::from_secs(3600)).await;
                 game_cache.cleanup(10000).await;
+                snapshot_cache.cleanup(100000).await;
+                // Drop steam history for markets idle longer than an hour (well past the
+                // detection window) so games that vanish pre-tipoff don't leak.
+                steam_detector.cleanup(chrono::Duration::hours(1)).await;
+            }
+        });
+
+        // Periodic OHLC candle aggregation: roll the last few buckets forward each
+        // interval so the `odds_candles` hypertable trails the live snapshot stream.
+        let candle_svc = Arc::clone(&self);
+        tokio::spawn(async move {
+            let bucket = Duration::from_secs(candle_svc.config.candle_bucket_seconds);
+            // Re-aggregate a generous lookback so late/out-of-order snapshots are picked up.
+            let lookback = bucket.saturating_mul(12).max(Duration::from_secs(900));
+            loop {
+                tokio::time::sleep(bucket).await;
+                if let Err(e) = candle_svc.aggregate_candles(lookback).await {
+                    warn!("Candle aggregation failed: {:?}", e);
+                }
             }
         });
 
@@ -1136,9 +2809,14 @@ impl OddsIngestionService {
 
     /// Single poll iteration
     async fn poll_once(&self) -> Result<usize> {
+        self.metrics.polls_total.inc();
+        let _timer = self.metrics.poll_duration.start_timer();
         // Step 1: Fetch full-game odds from the standard endpoint
         let events = self.fetch_events().await?;
-        let mut snapshots = self.process_events(events.clone()).await?;
+        let full_game = self.process_events(events.clone()).await?;
+        // `all` drives consensus/steam; `fresh` is what we store and publish to `odds.live`.
+        let mut all_snapshots = full_game.all;
+        let mut snapshots = full_game.fresh;
         let full_game_count = snapshots.len();
 
         // If we're running in one-shot mode (manual trigger), keep it fast and
@@ -1151,13 +2829,16 @@ impl OddsIngestionService {
                 full_game_count
             );
 
-            let (store_result, publish_result) = tokio::join!(
+            let consensus = self.compute_consensus(&all_snapshots);
+            let (store_result, publish_result, consensus_result) = tokio::join!(
                 self.store_snapshots(&snapshots),
-                self.publish_to_redis(&snapshots)
+                self.publish_to_redis(&snapshots),
+                self.publish_consensus_to_redis(&consensus)
             );
 
             store_result?;
             publish_result?;
+            consensus_result?;
 
             return Ok(snapshots.len());
         }
@@ -1165,36 +2846,63 @@ impl OddsIngestionService {
         // Step 2: Fetch first-half odds from the event-specific endpoint (premium)
         let event_ids: Vec<String> = events.iter().map(|e| e.id.clone()).collect();
         let h1_events = self.fetch_all_h1_odds(&event_ids).await?;
-        let h1_snapshots = self.process_events(h1_events).await?;
-        let h1_count = h1_snapshots.len();
-        snapshots.extend(h1_snapshots);
+        let h1 = self.process_events(h1_events).await?;
+        let h1_count = h1.fresh.len();
+        all_snapshots.extend(h1.all);
+        snapshots.extend(h1.fresh);
 
         // Step 3: Fetch second-half odds from the event-specific endpoint
         let h2_events = self.fetch_all_h2_odds(&event_ids).await?;
-        let h2_snapshots = self.process_events(h2_events).await?;
-        let h2_count = h2_snapshots.len();
-        snapshots.extend(h2_snapshots);
+        let h2 = self.process_events(h2_events).await?;
+        let h2_count = h2.fresh.len();
+        all_snapshots.extend(h2.all);
+        snapshots.extend(h2.fresh);
 
-        info!("Poll results: {} full-game + {} H1 + {} H2 = {} total snapshots", 
+        info!("Poll results: {} full-game + {} H1 + {} H2 = {} total snapshots",
               full_game_count, h1_count, h2_count, snapshots.len());
 
-        // Store and publish in parallel
-        let (store_result, publish_result) = tokio::join!(
+        // Detect steam moves from the full extracted set (not just the deduped subset) so
+        // consensus and steam see every book quoting this poll, then store/publish only fresh.
+        let consensus = self.compute_consensus(&all_snapshots);
+        let commence = self.build_commence_map(&events).await;
+        let alerts = self.steam_detector.detect(&consensus, &all_snapshots, &commence).await;
+
+        // Store and publish in parallel (per-book rows + cross-book consensus + steam alerts)
+        let (store_result, publish_result, consensus_result, steam_result) = tokio::join!(
             self.store_snapshots(&snapshots),
-            self.publish_to_redis(&snapshots)
+            self.publish_to_redis(&snapshots),
+            self.publish_consensus_to_redis(&consensus),
+            self.publish_steam_alerts(&alerts)
         );
 
         store_result?;
         publish_result?;
+        consensus_result?;
+        steam_result?;
 
         Ok(snapshots.len())
     }
+
+    /// Map internal game UUIDs to their `commence_time` for the current poll's events,
+    /// used by the steam detector to reset a game's history once it has tipped off.
+    async fn build_commence_map(&self, events: &[OddsApiEvent]) -> HashMap<Uuid, DateTime<Utc>> {
+        let mut map = HashMap::new();
+        for event in events {
+            if let (Some(game_id), Some(commence)) =
+                (self.game_cache.get(&event.id).await, event.commence_time)
+            {
+                map.insert(game_id, commence);
+            }
+        }
+        map
+    }
 }
 
 /// Health check handler
 async fn health_handler(
-    axum::extract::State(health): axum::extract::State<HealthState>,
+    axum::extract::State(service): axum::extract::State<Arc<OddsIngestionService>>,
 ) -> (StatusCode, Json<serde_json::Value>) {
+    let health = &service.health;
     let last_poll = health.last_poll_time.read().await;
     let last_count = health.last_poll_count.read().await;
     let errors = health.error_count.read().await;
@@ -1221,6 +2929,232 @@ async fn health_handler(
     })))
 }
 
+/// `GET /candles?game_id=&market=&period=` — OHLC line-movement series.
+///
+/// `game_id` is the external (feed) id; `market` is `spreads`/`totals`/`h2h` and `period`
+/// defaults to `full`. The bucket width is fixed at the configured `CANDLE_BUCKET_SECONDS`
+/// (the width the candles were aggregated at); there is no per-request `bucket` override —
+/// a mismatched width would fabricate or skip gap-fill windows against the stored candles.
+async fn candles_handler(
+    axum::extract::State(service): axum::extract::State<Arc<OddsIngestionService>>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let external_id = match params.get("game_id") {
+        Some(g) if !g.is_empty() => g.clone(),
+        _ => return (StatusCode::BAD_REQUEST, Json(json!({"error": "game_id is required"}))),
+    };
+    let market = params.get("market").map(|s| s.as_str()).unwrap_or("spreads").to_string();
+    let period = params.get("period").map(|s| s.as_str()).unwrap_or("full").to_string();
+
+    // Resolve the external id to our internal game UUID.
+    let game_id: Option<(Uuid,)> = match sqlx::query_as("SELECT id FROM games WHERE external_id = $1")
+        .bind(&external_id)
+        .fetch_optional(&service.db)
+        .await
+    {
+        Ok(row) => row,
+        Err(e) => {
+            error!("candles lookup failed: {:?}", e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "query failed"})));
+        }
+    };
+
+    let game_id = match game_id {
+        Some((id,)) => id,
+        None => return (StatusCode::NOT_FOUND, Json(json!({"error": "unknown game_id"}))),
+    };
+
+    match service.query_candles(game_id, &market, &period).await {
+        Ok(candles) => (StatusCode::OK, Json(json!({
+            "game_id": external_id,
+            "market": market,
+            "period": period,
+            "bucket_seconds": service.config.candle_bucket_seconds,
+            "candles": candles,
+        }))),
+        Err(e) => {
+            error!("candle query failed: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "query failed"})))
+        }
+    }
+}
+
+/// `GET /games/{external_id}/history?bookmaker=&market=&since=` — line-movement history.
+///
+/// Returns one time-ordered series per bookmaker/market/period for a single game, each with
+/// its open line, current line, and net movement, suitable for charting the market's
+/// evolution. `bookmaker` and `market` filter the series; `since` is an RFC 3339 timestamp
+/// lower bound.
+async fn game_history_handler(
+    axum::extract::State(service): axum::extract::State<Arc<OddsIngestionService>>,
+    axum::extract::Path(external_id): axum::extract::Path<String>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let bookmaker = params.get("bookmaker").map(|s| s.as_str()).filter(|s| !s.is_empty());
+    let market = params.get("market").map(|s| s.as_str()).filter(|s| !s.is_empty());
+    let since = match params.get("since") {
+        Some(s) if !s.is_empty() => match DateTime::parse_from_rfc3339(s) {
+            Ok(ts) => Some(ts.with_timezone(&Utc)),
+            Err(_) => {
+                return (StatusCode::BAD_REQUEST, Json(json!({"error": "since must be RFC 3339"})))
+            }
+        },
+        _ => None,
+    };
+
+    // Resolve the external id to our internal game UUID.
+    let game_id: Option<(Uuid,)> = match sqlx::query_as("SELECT id FROM games WHERE external_id = $1")
+        .bind(&external_id)
+        .fetch_optional(&service.db)
+        .await
+    {
+        Ok(row) => row,
+        Err(e) => {
+            error!("history lookup failed: {:?}", e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "query failed"})));
+        }
+    };
+
+    let game_id = match game_id {
+        Some((id,)) => id,
+        None => return (StatusCode::NOT_FOUND, Json(json!({"error": "unknown game_id"}))),
+    };
+
+    match service.query_line_history(game_id, bookmaker, market, since).await {
+        Ok(series) => (StatusCode::OK, Json(json!({
+            "game_id": external_id,
+            "series": series,
+        }))),
+        Err(e) => {
+            error!("history query failed: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "query failed"})))
+        }
+    }
+}
+
+/// `GET /stream?game_id=&market_type=` — Server-Sent Events feed of live snapshots.
+///
+/// Subscribes to the in-process fan-out hub (fed by a single Redis reader) and streams
+/// each matching snapshot as an SSE `data:` line. `game_id` matches either the external
+/// feed id or our internal UUID; both filters are optional.
+async fn stream_handler(
+    axum::extract::State(service): axum::extract::State<Arc<OddsIngestionService>>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Sse<impl futures_util::Stream<Item = Result<Event, std::convert::Infallible>>> {
+    let game_id = params.get("game_id").filter(|s| !s.is_empty()).cloned();
+    let market_type = params.get("market_type").filter(|s| !s.is_empty()).cloned();
+
+    let rx = service.stream_hub.subscribe();
+    let stream = BroadcastStream::new(rx).filter_map(move |result| {
+        let game_id = game_id.clone();
+        let market_type = market_type.clone();
+        async move {
+            // Drop lag errors silently; keep the subscriber connected.
+            let snapshot = result.ok()?;
+            if let Some(g) = &game_id {
+                if &snapshot.external_id != g && snapshot.game_id.to_string() != *g {
+                    return None;
+                }
+            }
+            if let Some(m) = &market_type {
+                if &snapshot.market_type != m {
+                    return None;
+                }
+            }
+            let data = serde_json::to_string(&snapshot).ok()?;
+            Some(Ok(Event::default().data(data)))
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Body for `POST /aliases`: bind a raw feed name to a canonical team.
+#[derive(Debug, Deserialize)]
+struct AliasRequest {
+    team_id: Uuid,
+    alias: String,
+    #[serde(default)]
+    source: Option<String>,
+}
+
+/// Body for `POST /teams/merge`: repoint a duplicate team onto the correct one.
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    from_team_id: Uuid,
+    to_team_id: Uuid,
+}
+
+/// Body for `POST /games`: inject or correct a game's team assignment.
+#[derive(Debug, Deserialize)]
+struct GameRequest {
+    external_id: String,
+    home_team_id: Uuid,
+    away_team_id: Uuid,
+    #[serde(default)]
+    commence_time: Option<DateTime<Utc>>,
+}
+
+/// `POST /aliases` — bind a feed name to a chosen team id.
+async fn aliases_handler(
+    axum::extract::State(service): axum::extract::State<Arc<OddsIngestionService>>,
+    Json(req): Json<AliasRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let source = req.source.as_deref().unwrap_or("manual");
+    match service.upsert_alias(req.team_id, &req.alias, source).await {
+        Ok(()) => (StatusCode::OK, Json(json!({"status": "ok", "team_id": req.team_id}))),
+        Err(e) => {
+            error!("alias upsert failed: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": e.to_string()})))
+        }
+    }
+}
+
+/// `POST /teams/merge` — merge a duplicate team into the correct one.
+async fn teams_merge_handler(
+    axum::extract::State(service): axum::extract::State<Arc<OddsIngestionService>>,
+    Json(req): Json<MergeRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    match service.merge_teams(req.from_team_id, req.to_team_id).await {
+        Ok(()) => (StatusCode::OK, Json(json!({"status": "ok", "team_id": req.to_team_id}))),
+        Err(e) => {
+            error!("team merge failed: {:?}", e);
+            (StatusCode::BAD_REQUEST, Json(json!({"error": e.to_string()})))
+        }
+    }
+}
+
+/// `POST /games` — inject or correct a game's team assignment.
+async fn games_handler(
+    axum::extract::State(service): axum::extract::State<Arc<OddsIngestionService>>,
+    Json(req): Json<GameRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    match service.upsert_game(&req.external_id, req.home_team_id, req.away_team_id, req.commence_time).await {
+        Ok(id) => (StatusCode::OK, Json(json!({"status": "ok", "game_id": id}))),
+        Err(e) => {
+            error!("game upsert failed: {:?}", e);
+            (StatusCode::BAD_REQUEST, Json(json!({"error": e.to_string()})))
+        }
+    }
+}
+
+/// `GET /metrics` — Prometheus text-format exposition.
+async fn metrics_handler(
+    axum::extract::State(service): axum::extract::State<Arc<OddsIngestionService>>,
+) -> (StatusCode, [(axum::http::header::HeaderName, &'static str); 1], String) {
+    let content_type = (
+        axum::http::header::CONTENT_TYPE,
+        "text/plain; version=0.0.4",
+    );
+    match service.metrics.render() {
+        Ok(body) => (StatusCode::OK, [content_type], body),
+        Err(e) => {
+            error!("metrics render failed: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, [content_type], String::new())
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // NO .env file loading - all secrets MUST come from Docker secret files
@@ -1238,15 +3172,35 @@ async fn main() -> Result<()> {
     let config = Config::from_env()?;
     let health_port = config.health_port;
     let run_once = config.run_once;
-    
-    let service = OddsIngestionService::new(config).await?;
-    let health_state = service.health.clone();
+    let is_backfill = config.is_backfill();
 
-    // Start health check server
+    let service = Arc::new(OddsIngestionService::new(config).await?);
+
+    // Fan-out reader: one task tails the Redis stream and broadcasts to SSE subscribers.
+    // Use a DEDICATED connection, never the multiplexed `service.redis`: the reader's
+    // `XREAD BLOCK` would otherwise hold the shared response slot and stall each poll's
+    // `XADD`s by up to the block timeout.
+    {
+        let reader_conn =
+            OddsIngestionService::connect_redis_with_retry(&service.config.redis_url, 5).await?;
+        let source = OddsStreamSource::redis(reader_conn, "odds.live");
+        let hub = service.stream_hub.clone();
+        tokio::spawn(run_stream_reader(source, hub));
+    }
+
+    // Start HTTP server (health, metrics, candle/history queries, live stream, and
+    // team/game correction API)
     let app = Router::new()
         .route("/health", get(health_handler))
-        .with_state(health_state);
-    
+        .route("/candles", get(candles_handler))
+        .route("/games/{external_id}/history", get(game_history_handler))
+        .route("/stream", get(stream_handler))
+        .route("/metrics", get(metrics_handler))
+        .route("/aliases", post(aliases_handler))
+        .route("/teams/merge", post(teams_merge_handler))
+        .route("/games", post(games_handler))
+        .with_state(Arc::clone(&service));
+
     let health_addr = format!("0.0.0.0:{}", health_port);
     info!("Health endpoint listening on {}", health_addr);
     
@@ -1256,6 +3210,19 @@ async fn main() -> Result<()> {
         axum::serve(listener, app).await.unwrap();
     });
 
+    // Backfill mode: drive the historical endpoint to completion and exit.
+    if is_backfill {
+        info!("Running in backfill mode (BACKFILL_FROM set)");
+        match service.run_backfill().await {
+            Ok(()) => info!("Backfill finished"),
+            Err(e) => {
+                error!("Backfill failed: {:?}", e);
+                return Err(e);
+            }
+        }
+        return Ok(());
+    }
+
     // Check if running in one-shot mode (manual trigger)
     if run_once {
         info!("Running in one-shot mode (RUN_ONCE=true)");
@@ -1288,3 +3255,61 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_snapshot(bookmaker: &str) -> OddsSnapshot {
+        OddsSnapshot {
+            time: Utc::now(),
+            game_id: Uuid::nil(),
+            external_id: "evt-1".to_string(),
+            bookmaker: bookmaker.to_string(),
+            market_type: "spreads".to_string(),
+            period: "full".to_string(),
+            home_line: Some(-3.5),
+            away_line: Some(3.5),
+            total_line: None,
+            home_price: Some(-110),
+            away_price: Some(-110),
+            over_price: None,
+            under_price: None,
+            home_fair_prob: None,
+            away_fair_prob: None,
+            over_fair_prob: None,
+            under_fair_prob: None,
+            overround: None,
+        }
+    }
+
+    /// The mock source feeds payloads through `run_stream_reader`; valid JSON is fanned out
+    /// to subscribers and a malformed entry between them is skipped without breaking the loop.
+    #[tokio::test]
+    async fn mock_reader_fans_out_valid_and_skips_malformed() {
+        let hub = StreamHub::new(16);
+        let mut rx = hub.subscribe();
+
+        let (tx, source_rx) = tokio::sync::mpsc::unbounded_channel();
+        let source = OddsStreamSource::mock(source_rx);
+        let reader = tokio::spawn(run_stream_reader(source, hub.clone()));
+
+        tx.send(serde_json::to_string(&sample_snapshot("draftkings")).unwrap()).unwrap();
+        tx.send("{ this is not valid json".to_string()).unwrap();
+        tx.send(serde_json::to_string(&sample_snapshot("fanduel")).unwrap()).unwrap();
+
+        let first = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("first snapshot not delivered in time")
+            .expect("hub closed");
+        let second = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .expect("second snapshot not delivered in time")
+            .expect("hub closed");
+
+        assert_eq!(first.bookmaker, "draftkings");
+        assert_eq!(second.bookmaker, "fanduel");
+
+        reader.abort();
+    }
+}